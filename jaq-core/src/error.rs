@@ -0,0 +1,86 @@
+//! Errors produced during filter evaluation.
+
+use crate::val::Val;
+use alloc::rc::Rc;
+use alloc::string::{String, ToString};
+use core::any::Any;
+use core::fmt;
+
+/// An error raised while running a filter.
+///
+/// This mirrors jq's `error(any)`: the erroring value itself is always a
+/// [`Val`], so that in-language `try`/`catch` can inspect it. A native
+/// filter that fails because of something outside jq's model (a missing
+/// file, a permission error, ...) can additionally attach a downcastable
+/// source payload, so that an embedder catching the error at the
+/// [`crate::Filter::run`] boundary can recover its own diagnostic type
+/// instead of only seeing a flattened message.
+#[derive(Clone)]
+pub struct Error {
+    val: Val,
+    source: Option<Rc<dyn Any + Send + Sync>>,
+}
+
+impl Error {
+    /// Raise `val` as an error, as `error(val)` would in jq.
+    pub fn new(val: Val) -> Self {
+        Self { val, source: None }
+    }
+
+    /// Raise `val` as an error, attaching a downcastable source payload.
+    ///
+    /// `val` is what in-language `try`/`catch` observes; `source` is what an
+    /// embedder can recover via [`Error::downcast_ref`].
+    pub fn with_source(val: Val, source: Rc<dyn Any + Send + Sync>) -> Self {
+        Self {
+            val,
+            source: Some(source),
+        }
+    }
+
+    /// The value carried by this error, as seen by in-language `try`/`catch`.
+    pub fn as_val(&self) -> &Val {
+        &self.val
+    }
+
+    /// Attempt to downcast the source payload back to its concrete type.
+    ///
+    /// Returns `None` if no source was attached, or if it doesn't match `T`.
+    pub fn downcast_ref<T: 'static>(&self) -> Option<&T> {
+        self.source.as_deref()?.downcast_ref::<T>()
+    }
+}
+
+impl From<String> for Error {
+    fn from(s: String) -> Self {
+        Self::new(Val::Str(Rc::new(s)))
+    }
+}
+
+impl From<&str> for Error {
+    fn from(s: &str) -> Self {
+        s.to_string().into()
+    }
+}
+
+impl PartialEq for Error {
+    /// Compares only the erroring [`Val`], ignoring `source`: the source
+    /// payload is an opaque `dyn Any` and generally isn't comparable, but
+    /// `Error` still needs to satisfy `PartialEq` for `ValR`/`Inputs` to be
+    /// comparable, e.g. in `assert_eq!`.
+    fn eq(&self, other: &Self) -> bool {
+        self.val == other.val
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.val)
+    }
+}
+
+impl fmt::Debug for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Error({:?})", self.val)
+    }
+}
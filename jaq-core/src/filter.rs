@@ -0,0 +1,120 @@
+//! Compiled filters and the native filters implemented in Rust.
+
+use crate::val::{Val, ValRs};
+use crate::Ctx;
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// The argument filters passed to a native filter of arity > 0.
+///
+/// [`Args::run`] runs the `i`th argument against a value exactly like the
+/// interpreter runs arguments for built-in higher-order filters such as
+/// `map`/`select`. Every call starts from the context this native filter
+/// was itself called with, stored once in `Args` rather than handed to
+/// the caller to thread through by hand, so that a native filter can be
+/// higher-order itself, e.g. a Rust-defined `debounce(f)` that decides
+/// when to re-run `f` on the host side, without variable bindings from
+/// one call leaking into the next.
+pub struct Args<'a> {
+    ctx: Ctx<'a>,
+    filters: &'a [Filter],
+}
+
+impl<'a> Args<'a> {
+    fn new(ctx: Ctx<'a>, filters: &'a [Filter]) -> Self {
+        Self { ctx, filters }
+    }
+
+    /// Run the `i`th argument filter against `val`.
+    ///
+    /// Panics if `i` is out of range for the arity this native filter was
+    /// registered with.
+    pub fn run(&self, i: usize, val: Val) -> ValRs<'a> {
+        self.filters[i].run((self.ctx.clone(), val))
+    }
+
+    /// The number of argument filters.
+    pub fn len(&self) -> usize {
+        self.filters.len()
+    }
+
+    /// Whether there are no argument filters.
+    pub fn is_empty(&self) -> bool {
+        self.filters.is_empty()
+    }
+}
+
+/// A filter implemented in Rust rather than defined in jq.
+///
+/// `Native` used to be a plain `fn` pointer, which made it impossible for a
+/// native filter to share state (a cache, a config, a set of credentials, ...)
+/// across calls. It is now a reference-counted closure, so that
+/// [`crate::Definitions::insert_custom`] can register filters that capture
+/// an environment. Plain `fn` pointers and non-capturing closures keep
+/// working, because `Native` implements `From` for any matching callable.
+#[derive(Clone)]
+pub struct Native(Arc<dyn for<'a> Fn(&Args<'a>, Ctx<'a>, Val) -> ValRs<'a> + Send + Sync>);
+
+impl Native {
+    /// Create a native filter from a closure or function pointer.
+    pub fn new(
+        f: impl for<'a> Fn(&Args<'a>, Ctx<'a>, Val) -> ValRs<'a> + Send + Sync + 'static,
+    ) -> Self {
+        Self(Arc::new(f))
+    }
+
+    /// Run this native filter on the given arguments, context and input value.
+    pub fn run<'a>(&self, args: &Args<'a>, ctx: Ctx<'a>, val: Val) -> ValRs<'a> {
+        (self.0)(args, ctx, val)
+    }
+}
+
+impl<F> From<F> for Native
+where
+    F: for<'a> Fn(&Args<'a>, Ctx<'a>, Val) -> ValRs<'a> + Send + Sync + 'static,
+{
+    fn from(f: F) -> Self {
+        Self::new(f)
+    }
+}
+
+impl fmt::Debug for Native {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Native(..)")
+    }
+}
+
+/// A compiled filter, ready for evaluation.
+#[derive(Clone, Debug, Default)]
+pub enum Filter {
+    /// The identity filter `.`.
+    #[default]
+    Id,
+    /// A call to a native filter, together with its argument filters.
+    Native(Native, Vec<Filter>),
+}
+
+impl Filter {
+    /// Run this filter on the given context and input value.
+    pub fn run<'a>(&'a self, (ctx, val): (Ctx<'a>, Val)) -> ValRs<'a> {
+        match self {
+            Self::Id => Box::new(core::iter::once(Ok(val))),
+            Self::Native(native, args) => native.run(&Args::new(ctx.clone(), args), ctx, val),
+        }
+    }
+}
+
+/// Build the table of core native filters; currently this is only `not`.
+///
+/// These do not depend on any host state; filters that do should be
+/// registered separately via [`crate::Definitions::insert_custom`].
+pub fn natives() -> Vec<(String, usize, Native)> {
+    alloc::vec![(
+        "not".to_string(),
+        0,
+        Native::new(|_args, _ctx, v| Box::new(core::iter::once(Ok(Val::Bool(!v.is_truthy())))))
+    )]
+}
@@ -0,0 +1,36 @@
+//! A boxed iterator that defers building its inner iterator until first polled.
+
+use alloc::boxed::Box;
+
+/// An iterator that runs a closure to produce its real iterator the first
+/// time it is polled, rather than when it is constructed.
+///
+/// Filter evaluation builds up chains of combinators (`|`, `,`, `//`, ...)
+/// up front; wrapping the tail of such a chain in `LazyIter` avoids
+/// recursing into it before a caller actually asks for its first value.
+pub struct LazyIter<'a, T> {
+    inner: Option<Box<dyn Iterator<Item = T> + 'a>>,
+    f: Option<Box<dyn FnOnce() -> Box<dyn Iterator<Item = T> + 'a> + 'a>>,
+}
+
+impl<'a, T> LazyIter<'a, T> {
+    /// Defer `f` until the first call to [`Iterator::next`].
+    pub fn new(f: impl FnOnce() -> Box<dyn Iterator<Item = T> + 'a> + 'a) -> Self {
+        Self {
+            inner: None,
+            f: Some(Box::new(f)),
+        }
+    }
+}
+
+impl<'a, T> Iterator for LazyIter<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.inner.is_none() {
+            let f = self.f.take().expect("LazyIter polled after being spent");
+            self.inner = Some(f());
+        }
+        self.inner.as_mut().unwrap().next()
+    }
+}
@@ -65,6 +65,7 @@ pub use rc_iter::RcIter;
 pub use val::{Val, ValR};
 
 use alloc::{
+    collections::BTreeMap,
     string::{String, ToString},
     vec::Vec,
 };
@@ -72,7 +73,7 @@ use lazy_iter::LazyIter;
 use parse::{Def, Main};
 use rc_list::RcList;
 
-type Inputs<'i> = RcIter<dyn Iterator<Item = Result<Val, String>> + 'i>;
+type Inputs<'i> = RcIter<dyn Iterator<Item = Result<Val, Error>> + 'i>;
 
 /// Filter execution context.
 #[derive(Clone)]
@@ -97,6 +98,28 @@ impl<'i> Ctx<'i> {
         self
     }
 
+    /// Bind this context's named globals from a name→value map.
+    ///
+    /// `names` must be [`Definitions::global_names`] for the `Definitions`
+    /// the filter was compiled from, so that each name is bound at the
+    /// slot [`Definitions::resolve_var`] reports for it. Names absent from
+    /// `globals` are bound to `null`. Call this after [`Ctx::new`] and
+    /// before running the filter; an embedder can refresh `globals` on
+    /// each invocation without reparsing the filter.
+    pub fn with_globals(mut self, names: &[String], globals: &BTreeMap<String, Val>) -> Self {
+        for name in names {
+            let v = globals.get(name).cloned().unwrap_or(Val::Null);
+            self.vars = self.vars.cons(v);
+        }
+        self
+    }
+
+    /// The variable bound at `slot` (0 = most recent), as numbered by
+    /// [`Definitions::resolve_var`].
+    pub fn var(&self, slot: usize) -> Option<&Val> {
+        self.vars.get(slot)
+    }
+
     /// Obtain and remove the `save` most recent variable bindings,
     /// then remove additional `skip` most recent bindings,
     /// finally add the original `save` bindings.
@@ -145,6 +168,37 @@ impl Definitions {
         Self(mir::Defs::new(vars))
     }
 
+    /// Declare a named runtime global.
+    ///
+    /// Unlike the positional `vars` passed to [`Definitions::new`], named
+    /// globals don't need to be supplied in parse order: bind their
+    /// values at run time with [`Ctx::with_globals`] and a name→value
+    /// map, and refresh them on each invocation without reparsing the
+    /// filter. A native filter reads a global with [`Ctx::var`] at the
+    /// slot given by [`Definitions::resolve_var`]; `$name` references in
+    /// jq source are not resolved to it by [`Definitions::finish`].
+    pub fn insert_global(&mut self, name: impl Into<String>) {
+        self.0.insert_global(name.into());
+    }
+
+    /// The names of globals declared via [`Definitions::insert_global`],
+    /// in declaration order, which is the order [`Ctx::with_globals`]
+    /// binds them in. Pass this to [`Ctx::with_globals`].
+    pub fn global_names(&self) -> &[String] {
+        self.0.globals()
+    }
+
+    /// Resolve `$name` to the stable runtime slot that [`Ctx::new`] and
+    /// [`Ctx::with_globals`] bind it at, whether `name` is one of the
+    /// positional `vars` or one of the declared globals.
+    ///
+    /// A native filter that wants to read a global directly can call this
+    /// once when it is registered, then read it with [`Ctx::var`] on every
+    /// invocation instead of comparing names.
+    pub fn resolve_var(&self, name: &str) -> Option<usize> {
+        lir::resolve_var(&self.0, name)
+    }
+
     /// Start out with only core filters, such as `length`, `keys`, ...
     ///
     /// Does not import filters from the standard library, such as `map`.
@@ -153,13 +207,25 @@ impl Definitions {
     }
 
     /// Add native filters with given names and arities.
-    pub fn insert_natives(
+    ///
+    /// As with [`Definitions::insert_custom`], each filter may be a plain
+    /// `fn` pointer or a closure that captures host state.
+    pub fn insert_natives<N: Into<filter::Native>>(
         &mut self,
-        natives: impl IntoIterator<Item = (String, usize, filter::Native)>,
+        natives: impl IntoIterator<Item = (String, usize, N)>,
     ) {
         natives
             .into_iter()
-            .for_each(|(name, arity, f)| self.0.insert_fn(name, arity, f))
+            .for_each(|(name, arity, f)| self.0.insert_fn(name, arity, f.into()))
+    }
+
+    /// Import a custom, Rust-defined filter.
+    ///
+    /// `filter` may be a plain `fn` pointer, or a closure that captures host
+    /// state (a cache, a config, credentials, ...), since anything that can
+    /// be called like a native filter converts into [`filter::Native`].
+    pub fn insert_custom(&mut self, name: &str, arity: usize, filter: impl Into<filter::Native>) {
+        self.0.insert_fn(name.to_string(), arity, filter.into());
     }
 
     /// Import parsed definitions, such as obtained from the standard library.
@@ -173,11 +239,6 @@ impl Definitions {
         defs.into_iter().for_each(|def| self.0.root_def(def, errs));
     }
 
-    /// Import a custom, Rust-defined filter.
-    pub fn insert_custom(&mut self, name: &str, arity: usize, filter: filter::Native) {
-        self.0.insert_fn(name.to_string(), arity, filter);
-    }
-
     /// Given a main filter (consisting of definitions and a body), return a finished filter.
     pub fn finish(mut self, (defs, body): Main, errs: &mut Vec<parse::Error>) -> Filter {
         self.insert_defs(defs, errs);
@@ -0,0 +1,74 @@
+//! Low-level IR: lowers resolved [`crate::mir::Defs`] into a runnable filter.
+
+use crate::filter::Filter;
+use crate::mir::Defs;
+use alloc::vec::Vec;
+
+/// The runtime slot a `$name` reference resolves to.
+///
+/// [`crate::Ctx::new`] conses positional `vars` in iteration order, so the
+/// *last* of them ends up at the front of the list (slot 0 from the
+/// front); [`crate::Ctx::with_globals`] then conses globals on top, in
+/// declaration order, so the *last declared* global ends up frontmost of
+/// all. Concretely, for `n` vars and `m` globals:
+/// - the global declared at position `i` (0 = first declared) sits at slot
+///   `m - 1 - i`;
+/// - the var declared at position `i` sits at slot `m + (n - 1 - i)`.
+///
+/// Resolving this here, once, at compile time means looking up a global or
+/// var at runtime is an O(1) index instead of a name comparison.
+pub(crate) fn resolve_var(defs: &Defs, name: &str) -> Option<usize> {
+    let globals = defs.globals();
+    if let Some(i) = globals.iter().position(|g| g == name) {
+        return Some(globals.len() - 1 - i);
+    }
+    let vars = defs.vars();
+    vars.iter()
+        .position(|v| v == name)
+        .map(|i| globals.len() + (vars.len() - 1 - i))
+}
+
+/// Produce the runnable filter and its recursive call table.
+///
+/// Each entry in the call table pairs the number of variables a recursive
+/// call must skip past (see `Ctx::save_skip_vars`) with the filter to run.
+///
+/// This returns the identity filter and an empty call table whatever
+/// `defs` holds: neither the definitions nor the main filter body are
+/// lowered, and `$name` references in them are not rewritten into
+/// [`resolve_var`]'s slot indices. [`resolve_var`] itself is available via
+/// [`crate::Definitions::resolve_var`], for a native filter that reads a
+/// global directly with [`crate::Ctx::var`].
+pub fn root_def(_defs: &Defs) -> (Filter, Vec<(usize, Filter)>) {
+    (Filter::Id, Vec::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Ctx, RcIter, Val};
+    use alloc::collections::BTreeMap;
+    use alloc::string::ToString;
+    use alloc::vec;
+
+    #[test]
+    fn resolves_vars_and_globals_to_their_own_slot() {
+        let mut defs = Defs::new(vec!["a".to_string(), "b".to_string()]);
+        defs.insert_global("g".to_string());
+
+        assert_eq!(resolve_var(&defs, "a"), Some(2));
+        assert_eq!(resolve_var(&defs, "b"), Some(1));
+        assert_eq!(resolve_var(&defs, "g"), Some(0));
+
+        let inputs = RcIter::new(core::iter::empty());
+        let mut globals = BTreeMap::new();
+        globals.insert("g".to_string(), Val::Num(3.0));
+
+        let ctx = Ctx::new([Val::Num(1.0), Val::Num(2.0)], &inputs)
+            .with_globals(&["g".to_string()], &globals);
+
+        assert_eq!(ctx.vars.get(resolve_var(&defs, "a").unwrap()), Some(&Val::Num(1.0)));
+        assert_eq!(ctx.vars.get(resolve_var(&defs, "b").unwrap()), Some(&Val::Num(2.0)));
+        assert_eq!(ctx.vars.get(resolve_var(&defs, "g").unwrap()), Some(&Val::Num(3.0)));
+    }
+}
@@ -0,0 +1,78 @@
+//! Mid-level IR: resolves names (definitions, variables, globals) before lowering.
+
+use crate::filter::Native;
+use alloc::string::String;
+use alloc::vec::Vec;
+use jaq_parse::{Def, Error as ParseError};
+
+/// A name bound to either a native filter or a jq-defined one.
+pub(crate) enum Binding {
+    /// A filter implemented in Rust.
+    Native(Native),
+    /// A filter defined in jq.
+    Def(Def),
+}
+
+/// Definitions, together with the positional variables and named globals
+/// that filters compiled from them may reference as `$name`.
+pub struct Defs {
+    vars: Vec<String>,
+    globals: Vec<String>,
+    bindings: Vec<((String, usize), Binding)>,
+    root: Option<Def>,
+}
+
+impl Defs {
+    /// Create a definition set with the given positional variable names.
+    pub fn new(vars: Vec<String>) -> Self {
+        Self {
+            vars,
+            globals: Vec::new(),
+            bindings: Vec::new(),
+            root: None,
+        }
+    }
+
+    /// The positional variable names, as passed to [`Defs::new`].
+    pub fn vars(&self) -> &[String] {
+        &self.vars
+    }
+
+    /// Declare a named runtime global. Globals are bound after `vars`, so
+    /// they take the slots in front of them, the last declared at slot 0
+    /// (see [`crate::lir::resolve_var`]).
+    pub fn insert_global(&mut self, name: String) {
+        self.globals.push(name);
+    }
+
+    /// The declared global names, in declaration order.
+    pub fn globals(&self) -> &[String] {
+        &self.globals
+    }
+
+    /// Register a native filter under `name/arity`.
+    pub fn insert_fn(&mut self, name: String, arity: usize, native: Native) {
+        self.bindings.push(((name, arity), Binding::Native(native)));
+    }
+
+    /// Register a jq-defined filter.
+    pub fn root_def(&mut self, def: Def, _errs: &mut Vec<ParseError>) {
+        self.bindings
+            .push(((def.name.clone(), def.args.len()), Binding::Def(def)));
+    }
+
+    /// Record the main filter body.
+    pub fn root_filter(&mut self, body: Def, _errs: &mut Vec<ParseError>) {
+        self.root = Some(body);
+    }
+
+    /// All bindings registered so far, under their `(name, arity)` key.
+    pub(crate) fn bindings(&self) -> &[((String, usize), Binding)] {
+        &self.bindings
+    }
+
+    /// The main filter body, if [`Defs::root_filter`] has been called.
+    pub(crate) fn root(&self) -> Option<&Def> {
+        self.root.as_ref()
+    }
+}
@@ -0,0 +1,52 @@
+//! A lazily-extended, reference-counted list over fallible input values.
+
+use crate::error::Error;
+use crate::val::Val;
+use crate::RcIter;
+use alloc::rc::Rc;
+use core::cell::OnceCell;
+
+type Item = Result<Val, Error>;
+type Inputs<'i> = RcIter<dyn Iterator<Item = Item> + 'i>;
+
+enum Cell<'i> {
+    Cons(Item, RcLazyList<'i>),
+    Nil,
+}
+
+/// A cons-list that pulls values from a shared input iterator on demand,
+/// caching each cell as it is forced.
+///
+/// Cloning an `RcLazyList` is O(1) and every clone observes the same
+/// underlying sequence exactly once, which lets several calls to the
+/// `input`/`inputs` builtins share progress through the same inputs.
+#[derive(Clone)]
+pub struct RcLazyList<'i> {
+    inputs: &'i Inputs<'i>,
+    cell: Rc<OnceCell<Cell<'i>>>,
+}
+
+impl<'i> RcLazyList<'i> {
+    /// Create a lazy list reading from the given shared input iterator.
+    pub fn new(inputs: &'i Inputs<'i>) -> Self {
+        Self {
+            inputs,
+            cell: Rc::new(OnceCell::new()),
+        }
+    }
+
+    fn force(&self) -> &Cell<'i> {
+        self.cell.get_or_init(|| match self.inputs.next() {
+            Some(v) => Cell::Cons(v, Self::new(self.inputs)),
+            None => Cell::Nil,
+        })
+    }
+
+    /// Split off the next value, if any, and the rest of the list.
+    pub fn next(&self) -> Option<(Item, RcLazyList<'i>)> {
+        match self.force() {
+            Cell::Cons(v, rest) => Some((v.clone(), rest.clone())),
+            Cell::Nil => None,
+        }
+    }
+}
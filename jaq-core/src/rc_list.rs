@@ -0,0 +1,67 @@
+//! A reference-counted, persistent singly-linked list.
+
+use alloc::rc::Rc;
+use alloc::vec::Vec;
+
+/// A persistent singly-linked list, cheap to clone and to extend.
+///
+/// Used for variable bindings in [`crate::Ctx`]: binding a new variable
+/// conses onto the front in O(1), and cloning a context to capture it in a
+/// closure is just bumping an `Rc`'s refcount.
+#[derive(Clone, Debug, Default)]
+pub enum RcList<T> {
+    /// The empty list.
+    #[default]
+    Nil,
+    /// A value followed by the rest of the list.
+    Cons(Rc<T>, Rc<RcList<T>>),
+}
+
+impl<T> RcList<T> {
+    /// Add `x` to the front of the list.
+    pub fn cons(self, x: T) -> Self {
+        Self::Cons(Rc::new(x), Rc::new(self))
+    }
+
+    /// Skip the `n` most recent bindings.
+    pub fn skip(&self, n: usize) -> &Self {
+        let mut cur = self;
+        for _ in 0..n {
+            match cur {
+                Self::Cons(_, rest) => cur = rest,
+                Self::Nil => break,
+            }
+        }
+        cur
+    }
+
+    /// Remove the `n` most recent bindings, returning them (most recent
+    /// first) along with what remains of the list.
+    pub fn pop_many(&self, n: usize) -> (Vec<&T>, &Self) {
+        let mut saved = Vec::with_capacity(n);
+        let mut cur = self;
+        for _ in 0..n {
+            match cur {
+                Self::Cons(x, rest) => {
+                    saved.push(&**x);
+                    cur = rest;
+                }
+                Self::Nil => break,
+            }
+        }
+        (saved, cur)
+    }
+
+    /// Add several values to the front of the list, in iteration order.
+    pub fn cons_many(self, xs: impl Iterator<Item = T>) -> Self {
+        xs.fold(self, Self::cons)
+    }
+
+    /// The value at position `i` from the front (0 = most recent), if any.
+    pub fn get(&self, i: usize) -> Option<&T> {
+        match self.skip(i) {
+            Self::Cons(x, _) => Some(x),
+            Self::Nil => None,
+        }
+    }
+}
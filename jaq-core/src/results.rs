@@ -0,0 +1,25 @@
+//! Combinators for composing streams of fallible values.
+
+use crate::error::Error;
+use crate::val::ValRs;
+use alloc::boxed::Box;
+
+/// Apply `f` to each successful value of `xs`, flattening the resulting
+/// streams, and short-circuit on the first error seen from either `xs`
+/// or `f`.
+///
+/// This is the shape shared by `|`, `,` and friends: a value either keeps
+/// flowing through the pipeline, or a single error ends it.
+pub fn then<'a, T: 'a>(
+    xs: impl Iterator<Item = Result<T, Error>> + 'a,
+    f: impl Fn(T) -> ValRs<'a> + 'a,
+) -> ValRs<'a> {
+    let ys = xs.flat_map(move |x| -> ValRs<'a> {
+        match x {
+            Ok(x) => f(x),
+            Err(e) => Box::new(core::iter::once(Err(e))),
+        }
+    });
+    let mut failed = false;
+    Box::new(ys.take_while(move |y| !core::mem::replace(&mut failed, y.is_err())))
+}
@@ -0,0 +1,203 @@
+//! JSON values, plus an escape hatch for opaque host data.
+
+use crate::error::Error;
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::any::Any;
+use core::fmt;
+use serde_json::Value;
+
+/// A value flowing through a filter pipeline.
+///
+/// Most values are plain JSON, represented the same way `serde_json`
+/// represents them. [`Val::Foreign`] is the exception: it carries an
+/// arbitrary host-defined Rust value (a file handle, a parsed `Metadata`,
+/// a precompiled matcher, ...) that native filters can produce and
+/// downstream native filters can downcast back out, while ordinary jq code
+/// still sees a degraded JSON view of it via [`Foreign::to_json`].
+#[derive(Clone)]
+pub enum Val {
+    /// `null`
+    Null,
+    /// `true` or `false`
+    Bool(bool),
+    /// A number
+    Num(f64),
+    /// A string
+    Str(Rc<String>),
+    /// An array
+    Arr(Rc<Vec<Val>>),
+    /// An object, as an ordered list of key-value pairs
+    Obj(Rc<Vec<(Rc<String>, Val)>>),
+    /// An opaque, non-JSON value produced by a native filter
+    Foreign(Rc<Foreign>),
+}
+
+/// An opaque value carried by [`Val::Foreign`], along with the operations
+/// jaq needs to perform on it without knowing its concrete type.
+pub struct Foreign {
+    /// The wrapped host value, downcastable via [`Foreign::downcast_ref`].
+    value: Rc<dyn Any>,
+    /// A human-readable type name, e.g. `"Metadata"`.
+    type_name: &'static str,
+    display: fn(&dyn Any) -> String,
+    eq: fn(&dyn Any, &dyn Any) -> bool,
+    to_json: Option<fn(&dyn Any) -> Value>,
+}
+
+impl Foreign {
+    /// Wrap a host value as a foreign value.
+    ///
+    /// `to_json` is an optional fallback used when jq code (or serialization)
+    /// demands a concrete JSON view; when absent, the foreign value
+    /// serializes as `{"type": type_name}`.
+    pub fn new<T: 'static + PartialEq + fmt::Display>(
+        value: T,
+        type_name: &'static str,
+        to_json: Option<fn(&T) -> Value>,
+    ) -> Self {
+        let display: fn(&dyn Any) -> String =
+            |a| a.downcast_ref::<T>().expect("type mismatch").to_string();
+        let eq: fn(&dyn Any, &dyn Any) -> bool = |a, b| {
+            match (a.downcast_ref::<T>(), b.downcast_ref::<T>()) {
+                (Some(a), Some(b)) => a == b,
+                _ => false,
+            }
+        };
+        let to_json = to_json.map(|f| {
+            let f: fn(&dyn Any) -> Value =
+                move |a| f(a.downcast_ref::<T>().expect("type mismatch"));
+            f
+        });
+        Self {
+            value: Rc::new(value),
+            type_name,
+            display,
+            eq,
+            to_json,
+        }
+    }
+
+    /// The type name given at construction, e.g. `"Metadata"`.
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// Attempt to downcast the wrapped value back to its concrete type.
+    pub fn downcast_ref<T: 'static>(&self) -> Option<&T> {
+        self.value.downcast_ref::<T>()
+    }
+
+    /// Render the degraded JSON view seen by ordinary jq code.
+    pub fn to_json(&self) -> Value {
+        match self.to_json {
+            Some(f) => f(&*self.value),
+            None => serde_json::json!({ "type": self.type_name }),
+        }
+    }
+}
+
+impl fmt::Display for Foreign {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&(self.display)(&*self.value))
+    }
+}
+
+impl fmt::Debug for Foreign {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Foreign({})", self.type_name)
+    }
+}
+
+impl PartialEq for Foreign {
+    fn eq(&self, other: &Self) -> bool {
+        (self.eq)(&*self.value, &*other.value)
+    }
+}
+
+/// Result of evaluating a filter on a single value.
+pub type ValR = Result<Val, Error>;
+
+/// Stream of [`ValR`], as produced by running a filter.
+pub type ValRs<'a> = Box<dyn Iterator<Item = ValR> + 'a>;
+
+impl Val {
+    /// Wrap a host value as a [`Val::Foreign`].
+    pub fn foreign<T: 'static + PartialEq + fmt::Display>(
+        value: T,
+        type_name: &'static str,
+        to_json: Option<fn(&T) -> Value>,
+    ) -> Self {
+        Self::Foreign(Rc::new(Foreign::new(value, type_name, to_json)))
+    }
+
+    /// Whether this value is truthy, as jq defines it (everything but `false` and `null`).
+    pub fn is_truthy(&self) -> bool {
+        !matches!(self, Val::Null | Val::Bool(false))
+    }
+
+    /// Render this value as plain JSON, using [`Foreign::to_json`] as a fallback.
+    pub fn to_json(&self) -> Value {
+        match self {
+            Val::Null => Value::Null,
+            Val::Bool(b) => Value::from(*b),
+            Val::Num(n) => serde_json::Number::from_f64(*n).map_or(Value::Null, Value::from),
+            Val::Str(s) => Value::from(s.as_str()),
+            Val::Arr(a) => Value::from_iter(a.iter().map(Val::to_json)),
+            Val::Obj(o) => Value::from_iter(o.iter().map(|(k, v)| (k.to_string(), v.to_json()))),
+            Val::Foreign(f) => f.to_json(),
+        }
+    }
+}
+
+impl From<Value> for Val {
+    fn from(v: Value) -> Self {
+        match v {
+            Value::Null => Val::Null,
+            Value::Bool(b) => Val::Bool(b),
+            Value::Number(n) => Val::Num(n.as_f64().unwrap_or(f64::NAN)),
+            Value::String(s) => Val::Str(Rc::new(s)),
+            Value::Array(a) => Val::Arr(Rc::new(a.into_iter().map(Val::from).collect())),
+            Value::Object(o) => Val::Obj(Rc::new(
+                o.into_iter()
+                    .map(|(k, v)| (Rc::new(k), Val::from(v)))
+                    .collect(),
+            )),
+        }
+    }
+}
+
+impl PartialEq for Val {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Val::Null, Val::Null) => true,
+            (Val::Bool(a), Val::Bool(b)) => a == b,
+            (Val::Num(a), Val::Num(b)) => a == b,
+            (Val::Str(a), Val::Str(b)) => a == b,
+            (Val::Arr(a), Val::Arr(b)) => a == b,
+            (Val::Obj(a), Val::Obj(b)) => a == b,
+            (Val::Foreign(a), Val::Foreign(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for Val {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Val::Foreign(x) => x.fmt(f),
+            other => write!(f, "{}", other.to_json()),
+        }
+    }
+}
+
+impl fmt::Debug for Val {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Val::Foreign(x) => x.fmt(f),
+            other => write!(f, "{:?}", other.to_json()),
+        }
+    }
+}